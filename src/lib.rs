@@ -38,13 +38,37 @@
 //! assert_eq!(parser.advance('h'), ParserState::Url(19));
 //! ```
 //!
+//! For cases where the origin of a URL is already known, such as the column a mouse click landed
+//! on, [`CursorParser`] grows a match outward from that position instead of scanning a whole
+//! buffer.
+//!
+//! Since [`Parser`] is fed characters rather than holding the original string, it cannot return
+//! the matched URL's text on its own. [`Parser::with_capture`] opts into buffering the consumed
+//! characters so [`Parser::captured`] can return the text directly.
+//!
+//! By default the reported length is a character count, which diverges from the number of
+//! terminal cells a URL occupies once CJK characters, emoji, or combining marks are involved.
+//! [`Parser::with_display_width`] reports the length in columns instead, so it lines up with
+//! rendered text.
+//!
+//! Only a fixed set of schemes is recognized by default. [`Parser::with_schemes`] accepts a
+//! custom list instead, for protocols like `magnet:` or application-specific schemes.
+//!
+//! By default any `scheme://` is accepted regardless of what follows, so something like
+//! `https://]]]` can still be reported as a URL. [`Parser::with_authority_validation`] checks the
+//! authority once its boundary is reached, rejecting hosts that are empty, contain illegal
+//! characters, or have a malformed bracketed IPv6 literal.
+//!
 //! [`chars`]: https://doc.rust-lang.org/std/primitive.char.html
 //! [`ParserState::MaybeUrl`]: enum.ParserState.html#variant.MaybeUrl
 //! [`ParserState::NoUrl`]: enum.ParserState.html#variant.NoUrl
 //! [`Parser`]: struct.Parser.html
+//! [`CursorParser`]: struct.CursorParser.html
 
 #![cfg_attr(all(test, feature = "bench"), feature(test))]
 
+use unicode_width::UnicodeWidthChar;
+
 #[cfg(test)]
 mod tests;
 
@@ -90,13 +114,44 @@ pub enum ParserState {
 ///
 /// The URL parser takes characters of a string **in reverse order** and returns the length of the
 /// URL whenever finding one.
-#[derive(Default)]
 pub struct Parser {
-    pub(crate) scheme_indices: [u8; 8],
+    pub(crate) scheme_indices: Vec<u8>,
     pub(crate) state: State,
 
-    surround_states: Vec<(char, usize)>,
+    surround_states: Vec<(char, usize, usize)>,
     len: usize,
+    char_len: usize,
+
+    capture: Option<Vec<char>>,
+    captured_url: Option<String>,
+    display_width: bool,
+
+    schemes: Vec<String>,
+    scheme_conflicts: bool,
+    pending_len: Option<usize>,
+
+    validate_authority: bool,
+    authority_buffer: Vec<char>,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self {
+            scheme_indices: vec![0; SCHEMES.len()],
+            state: State::default(),
+            surround_states: Vec::new(),
+            len: 0,
+            char_len: 0,
+            capture: None,
+            captured_url: None,
+            display_width: false,
+            schemes: SCHEMES.iter().map(|scheme| (*scheme).to_owned()).collect(),
+            scheme_conflicts: false,
+            pending_len: None,
+            validate_authority: false,
+            authority_buffer: Vec::new(),
+        }
+    }
 }
 
 impl Parser {
@@ -114,6 +169,138 @@ impl Parser {
         Self::default()
     }
 
+    /// Opts into capturing the matched URL's text.
+    ///
+    /// This keeps every character advanced into an internal buffer, so the matched URL's text
+    /// can be read back with [`Parser::captured`] instead of having to re-slice the original
+    /// input by the reported length. Combines with the other `with_*` builder methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rfind_url::{Parser, ParserState};
+    ///
+    /// let mut parser = Parser::new().with_capture();
+    ///
+    /// for c in "ttps://example.org".chars().rev() {
+    ///     parser.advance(c);
+    /// }
+    ///
+    /// assert_eq!(parser.advance('h'), ParserState::Url(19));
+    /// assert_eq!(parser.captured(), Some("https://example.org"));
+    /// ```
+    #[inline]
+    pub fn with_capture(mut self) -> Self {
+        self.capture = Some(Vec::new());
+        self
+    }
+
+    /// Restricts recognized schemes to the given list.
+    ///
+    /// This replaces the default scheme list, which unblocks non-default protocols like
+    /// `magnet:` or custom application schemes without forking the crate. Combines with the
+    /// other `with_*` builder methods.
+    ///
+    /// Matching a scheme that is a suffix of another scheme in the list (e.g. `"ttp"` alongside
+    /// `"http"`) still works, but falls back to a slower scan which keeps matching until a
+    /// non-letter character confirms no longer scheme is still in progress. Such a match is
+    /// reported by the call that advances that non-letter character, so a match at the very
+    /// start of the input, with no character advanced after its scheme, is not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rfind_url::{Parser, ParserState};
+    ///
+    /// let mut parser = Parser::new().with_schemes(&["magnet", "ipfs"]);
+    ///
+    /// for c in "agnet://example".chars().rev() {
+    ///     parser.advance(c);
+    /// }
+    ///
+    /// assert_eq!(parser.advance('m'), ParserState::Url(16));
+    /// ```
+    #[inline]
+    pub fn with_schemes(mut self, schemes: &[&str]) -> Self {
+        self.scheme_conflicts = schemes
+            .iter()
+            .enumerate()
+            .any(|(i, scheme)| schemes.iter().enumerate().any(|(j, other)| i != j && scheme.ends_with(other)));
+        self.schemes = schemes.iter().map(|scheme| (*scheme).to_owned()).collect();
+        self.scheme_indices = vec![0; schemes.len()];
+
+        self
+    }
+
+    /// Reports the matched length in terminal display columns instead of characters.
+    ///
+    /// Instead of counting characters, this increments the reported length by each character's
+    /// [Unicode display width], so double-width CJK characters and emoji count for two columns
+    /// while zero-width combining marks attach to the preceding column instead of starting a new
+    /// one. Combines with the other `with_*` builder methods, including [`Parser::with_capture`]
+    /// — the captured text is still indexed in characters, independently of the reported length.
+    ///
+    /// [Unicode display width]: https://docs.rs/unicode-width
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rfind_url::{Parser, ParserState};
+    ///
+    /// let mut parser = Parser::new().with_display_width();
+    ///
+    /// for c in "ttps://例.org".chars().rev() {
+    ///     parser.advance(c);
+    /// }
+    ///
+    /// // "例" occupies two columns, despite being a single character.
+    /// assert_eq!(parser.advance('h'), ParserState::Url(14));
+    /// ```
+    #[inline]
+    pub fn with_display_width(mut self) -> Self {
+        self.display_width = true;
+        self
+    }
+
+    /// Opts into validating the authority once its boundary is reached.
+    ///
+    /// Without this, any `scheme://` is accepted no matter what follows it, so something like
+    /// `https://]]]` is still reported as a URL. This validates the host as soon as the parser
+    /// has finished consuming it: a bracketed IPv6 literal must contain only hex digits, colons
+    /// and an optional `%zone`, a bare host must contain at least one non-empty label made up of
+    /// host-legal characters, and an empty host is always rejected. A failing authority makes
+    /// [`Parser::advance`] return [`ParserState::NoUrl`] and reset, instead of reporting a match.
+    /// Combines with the other `with_*` builder methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rfind_url::{Parser, ParserState};
+    ///
+    /// let mut parser = Parser::new().with_authority_validation();
+    /// let mut matched = false;
+    ///
+    /// for c in "https://exa,mple.org".chars().rev() {
+    ///     matched |= matches!(parser.advance(c), ParserState::Url(_));
+    /// }
+    ///
+    /// assert!(!matched);
+    /// ```
+    #[inline]
+    pub fn with_authority_validation(mut self) -> Self {
+        self.validate_authority = true;
+        self
+    }
+
+    /// Returns the text of the most recently matched URL.
+    ///
+    /// This is only populated when the parser was created with [`Parser::with_capture`], and is
+    /// cleared again on the next call to [`Parser::advance`].
+    #[inline]
+    pub fn captured(&self) -> Option<&str> {
+        self.captured_url.as_deref()
+    }
+
     /// Advances the parser by one character.
     ///
     /// # Examples
@@ -136,7 +323,25 @@ impl Parser {
     /// ```
     #[inline]
     pub fn advance(&mut self, c: char) -> ParserState {
-        self.len += 1;
+        // A conflicting scheme match (see `scheme_conflicts`) is only finalized once a
+        // non-letter confirms no longer scheme is still in progress. That character is not part
+        // of the URL, so surface the pending match before it is counted, captured, or handled as
+        // an illegal or surrounding character.
+        if let Some(length) = self.pending_len.filter(|_| self.state == State::Scheme) {
+            if !c.is_ascii_alphabetic() {
+                self.captured_url = self.captured_text();
+                self.reset();
+                return ParserState::Url(length);
+            }
+        }
+
+        self.len += self.char_width(c);
+        self.char_len += 1;
+        self.captured_url = None;
+
+        if let Some(capture) = &mut self.capture {
+            capture.push(c);
+        }
 
         if is_illegal(c) {
             self.reset();
@@ -150,6 +355,7 @@ impl Parser {
         if let Some((index, elem)) = m {
             if elem.1 + 1 < self.len {
                 self.surround_states.remove(index);
+                self.push_authority(c);
                 return ParserState::MaybeUrl;
             }
         }
@@ -158,7 +364,8 @@ impl Parser {
         for surround_char in &SURROUND_CHARACTERS {
             // Store surrounding to find a match in the future
             if m.is_none() && surround_char.start() == &c {
-                self.surround_states.push((*surround_char.end(), self.len));
+                self.surround_states.push((*surround_char.end(), self.len, self.char_len));
+                self.push_authority(c);
                 return ParserState::MaybeUrl;
             }
 
@@ -173,9 +380,14 @@ impl Parser {
             State::Default => self.advance_default(c),
             State::Path => self.advance_path(c),
             State::SchemeFirstSlash => self.advance_scheme_first_slash(c),
-            State::SchemeSecondSlash => self.advance_scheme_second_slash(c),
+            State::SchemeSecondSlash => {
+                if !self.advance_scheme_second_slash(c) {
+                    return ParserState::NoUrl;
+                }
+            },
             State::Scheme => {
                 if let Some(length) = self.advance_scheme(c) {
+                    self.captured_url = self.captured_text();
                     self.reset();
                     return ParserState::Url(length);
                 }
@@ -188,6 +400,20 @@ impl Parser {
         }
     }
 
+    /// Returns how much a character should add to the reported length.
+    ///
+    /// With [`Parser::with_display_width`] this is the character's Unicode display width, so
+    /// zero-width combining marks attach to the preceding column instead of starting a new one.
+    /// Otherwise every character counts as exactly one.
+    #[inline]
+    fn char_width(&self, c: char) -> usize {
+        if self.display_width {
+            UnicodeWidthChar::width(c).unwrap_or(0)
+        } else {
+            1
+        }
+    }
+
     /// Reset the parser to its initial state.
     ///
     /// # Examples
@@ -211,16 +437,51 @@ impl Parser {
     #[inline]
     pub fn reset(&mut self) {
         self.surround_states.clear();
-        self.scheme_indices = [0; 8];
+        for index in &mut self.scheme_indices {
+            *index = 0;
+        }
         self.state = State::Default;
         self.len = 0;
+        self.char_len = 0;
+        self.pending_len = None;
+        self.authority_buffer.clear();
+
+        if let Some(capture) = &mut self.capture {
+            capture.clear();
+        }
+    }
+
+    /// Push a character consumed before the scheme onto the authority buffer.
+    ///
+    /// This is a no-op unless [`Parser::with_authority_validation`] was used, and once the
+    /// scheme itself is being matched the authority is already complete.
+    #[inline]
+    fn push_authority(&mut self, c: char) {
+        if self.validate_authority && self.state != State::Scheme {
+            self.authority_buffer.push(c);
+        }
+    }
+
+    /// Re-reverse the captured buffer into the matched URL's text.
+    ///
+    /// This discards the same unmatched surrounding characters that [`Parser::advance_scheme`]
+    /// already excludes from the reported length, but trims by *character* count rather than by
+    /// that length: under [`Parser::with_display_width`] the reported length is a column count,
+    /// while `capture` is indexed one element per character, so the two can't be mixed.
+    fn captured_text(&self) -> Option<String> {
+        let capture = self.capture.as_ref()?;
+        let trim = self.surround_states.last().map(|s| s.2).unwrap_or(0);
+        Some(capture[trim..].iter().rev().collect())
     }
 
     #[inline]
     fn advance_default(&mut self, c: char) {
         match c {
             '.' | ',' | ':'..=';' | '?' | '!' | '(' => self.reset(),
-            _ => self.state = State::Path,
+            _ => {
+                self.state = State::Path;
+                self.push_authority(c);
+            },
         }
     }
 
@@ -228,6 +489,8 @@ impl Parser {
     fn advance_path(&mut self, c: char) {
         if c == '/' {
             self.state = State::SchemeFirstSlash
+        } else {
+            self.push_authority(c);
         }
     }
 
@@ -237,46 +500,81 @@ impl Parser {
             self.state = State::SchemeSecondSlash;
         } else {
             self.state = State::Path;
+            self.authority_buffer.clear();
+            self.push_authority(c);
         }
     }
 
+    /// Advance the state machine past the second slash of `://`.
+    ///
+    /// Returns `false` when [`Parser::with_authority_validation`] rejects the authority that was
+    /// just completed; the caller must then report [`ParserState::NoUrl`] without matching.
     #[inline]
-    fn advance_scheme_second_slash(&mut self, c: char) {
+    fn advance_scheme_second_slash(&mut self, c: char) -> bool {
         if c == ':' {
+            // Unmatched surrounding characters and everything consumed before them are excluded
+            // from the reported URL, so they are not part of its authority either. Apart from the
+            // `://` itself, every character consumed since the last one is in the buffer.
+            let authority = match self.surround_states.last() {
+                Some(s) => {
+                    let len = self.char_len.saturating_sub(s.2 + 3);
+                    &self.authority_buffer[self.authority_buffer.len().saturating_sub(len)..]
+                },
+                None => &self.authority_buffer[..],
+            };
+
+            if self.validate_authority && !is_valid_authority(authority) {
+                self.reset();
+                return false;
+            }
+
             self.state = State::Scheme;
         } else {
             self.state = State::Path;
+            self.authority_buffer.clear();
+            self.push_authority(c);
         }
+
+        true
     }
 
     #[inline]
     fn advance_scheme(&mut self, c: char) -> Option<usize> {
         match c {
             'a'..='z' | 'A'..='Z' => {
-                for (i, index) in self.scheme_indices.iter_mut().enumerate() {
-                    let scheme_len = SCHEMES[i].len() as u8;
+                for i in 0..self.scheme_indices.len() {
+                    let index = self.scheme_indices[i];
+                    let scheme_len = self.schemes[i].len() as u8;
 
-                    if *index >= scheme_len {
+                    if index >= scheme_len {
                         continue;
                     }
 
-                    if SCHEMES[i].chars().rev().nth(*index as usize) != Some(c) {
-                        *index = scheme_len + 1;
-                    } else {
-                        *index += 1;
-                    }
+                    let matched = self.schemes[i].chars().rev().nth(index as usize) == Some(c);
+                    self.scheme_indices[i] = if matched { index + 1 } else { scheme_len + 1 };
+
+                    if self.scheme_indices[i] == scheme_len {
+                        // Truncate the length to exclude all unmatched surroundings.
+                        let length = self.len - self.surround_states.last().map(|s| s.1).unwrap_or(0);
 
-                    // Returning early here is only possible because no scheme ends with another
-                    // scheme. This is covered by the `no_scheme_conflicts` test.
-                    if *index == scheme_len {
-                        // Truncate the length to exclude all unmatched surroundings
-                        self.len -= self.surround_states.last().map(|s| s.1).unwrap_or(0);
+                        // Returning early here is only correct when no scheme ends with another
+                        // scheme, as checked by `scheme_conflicts`. Otherwise a longer scheme
+                        // sharing this suffix might still be in progress.
+                        if !self.scheme_conflicts {
+                            return Some(length);
+                        }
 
-                        return Some(self.len);
+                        self.pending_len = Some(length);
                     }
                 }
             },
-            _ => self.reset(),
+            _ => {
+                if let Some(length) = self.pending_len {
+                    return Some(length);
+                }
+
+                self.reset();
+            },
         }
 
         None
@@ -302,6 +600,213 @@ fn is_illegal(c: char) -> bool {
     }
 }
 
+/// Checks whether a buffered authority is a well-formed host, with an optional `:port` suffix.
+///
+/// `buffer` holds the authority's characters in reverse order, as consumed by the parser. A query
+/// or fragment following the authority directly, and any `userinfo@` before the host, are not
+/// part of the host and are not checked.
+/// Borrows the IPv6-in-brackets and empty-host rules from the WHATWG/rust-url host grammar: a
+/// bracketed literal may only contain hex digits, colons and an optional `%zone`, and a bare host
+/// must have at least one non-empty label made up of host-legal characters.
+fn is_valid_authority(buffer: &[char]) -> bool {
+    let authority: String = buffer.iter().rev().collect();
+    let authority = authority.split(|c| c == '?' || c == '#').next().unwrap_or("");
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+
+    if authority.is_empty() {
+        return false;
+    }
+
+    if let Some(rest) = authority.strip_prefix('[') {
+        let end = match rest.find(']') {
+            Some(end) => end,
+            None => return false,
+        };
+        let literal = &rest[..end];
+        let port = match rest[end + 1..].strip_prefix(':') {
+            Some(port) => port,
+            None if rest[end + 1..].is_empty() => "",
+            None => return false,
+        };
+
+        if literal.is_empty() || !is_valid_port(port) {
+            return false;
+        }
+
+        return match literal.split_once('%') {
+            Some((address, zone)) => !zone.is_empty() && is_hex_and_colons(address),
+            None => is_hex_and_colons(literal),
+        };
+    }
+
+    let (host, port) = authority.split_once(':').unwrap_or((authority, ""));
+
+    if host.is_empty() || !is_valid_port(port) {
+        return false;
+    }
+
+    host.split('.').all(|label| !label.is_empty() && label.chars().all(is_host_char))
+}
+
+#[inline]
+fn is_hex_and_colons(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c == ':' || c.is_ascii_hexdigit())
+}
+
+#[inline]
+fn is_valid_port(port: &str) -> bool {
+    port.chars().all(|c| c.is_ascii_digit())
+}
+
+#[inline]
+fn is_host_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '~') || !c.is_ascii()
+}
+
+/// Parser which expands a URL match outward from a known position inside it.
+///
+/// Unlike [`Parser`], which scans a full buffer strictly in reverse, [`CursorParser`] is built
+/// for "click-to-open" use cases: the caller already knows a single character that is part of a
+/// URL (for example the column a mouse click landed on) and grows the match to the left and to
+/// the right of that origin until both boundaries are found.
+///
+/// # Examples
+///
+/// ```
+/// use rfind_url::CursorParser;
+///
+/// let mut parser = CursorParser::new();
+///
+/// parser.advance_origin('h');
+/// for c in "ttps://example.org".chars() {
+///     parser.advance_right(c);
+/// }
+///
+/// assert_eq!(parser.url(), Some((0, 18)));
+/// ```
+#[derive(Default)]
+pub struct CursorParser {
+    buffer: Vec<char>,
+    origin: usize,
+    left_done: bool,
+    right_done: bool,
+    left_surround: Vec<char>,
+    right_surround: Vec<char>,
+}
+
+impl CursorParser {
+    /// Creates a new cursor parser.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the character at the cursor's origin.
+    ///
+    /// This must be called exactly once, before any calls to [`CursorParser::advance_left`] or
+    /// [`CursorParser::advance_right`].
+    #[inline]
+    pub fn advance_origin(&mut self, c: char) {
+        self.buffer.push(c);
+    }
+
+    /// Grow the match by one character to the left of the origin.
+    ///
+    /// Returns `false` once this side of the match can no longer grow; the character was not
+    /// consumed and this method should not be called again for this side.
+    pub fn advance_left(&mut self, c: char) -> bool {
+        if self.left_done || is_illegal(c) {
+            self.left_done = true;
+            return false;
+        }
+
+        if let Some(index) = self.left_surround.iter().rposition(|&pending| pending == c) {
+            self.left_surround.remove(index);
+        } else if let Some(surround) = SURROUND_CHARACTERS.iter().find(|s| *s.start() == c) {
+            self.left_surround.push(*surround.end());
+        } else if SURROUND_CHARACTERS.iter().any(|s| *s.end() == c) {
+            self.left_done = true;
+            return false;
+        }
+
+        self.buffer.insert(0, c);
+        self.origin += 1;
+        true
+    }
+
+    /// Grow the match by one character to the right of the origin.
+    ///
+    /// Returns `false` once this side of the match can no longer grow; the character was not
+    /// consumed and this method should not be called again for this side.
+    pub fn advance_right(&mut self, c: char) -> bool {
+        if self.right_done || is_illegal(c) {
+            self.right_done = true;
+            return false;
+        }
+
+        if let Some(index) = self.right_surround.iter().rposition(|&pending| pending == c) {
+            self.right_surround.remove(index);
+        } else if let Some(surround) = SURROUND_CHARACTERS.iter().find(|s| *s.end() == c) {
+            self.right_surround.push(*surround.start());
+        } else if SURROUND_CHARACTERS.iter().any(|s| *s.start() == c) {
+            self.right_done = true;
+            return false;
+        }
+
+        self.buffer.push(c);
+        true
+    }
+
+    /// Find the URL span around the origin, from the characters fed so far.
+    ///
+    /// Returns the number of characters matched to the left and to the right of the origin, or
+    /// `None` if the origin isn't part of a URL.
+    pub fn url(&self) -> Option<(usize, usize)> {
+        // Deny-end characters must never trail a URL, even mid-match.
+        let mut end = self.buffer.len();
+        while end > 0 && is_deny_end(self.buffer[end - 1]) {
+            end -= 1;
+        }
+
+        if end == 0 || self.origin >= end {
+            return None;
+        }
+
+        // Find the rightmost scheme match that still starts at or before the origin, in char
+        // indices throughout so multi-byte characters in the buffer can't desync the offsets.
+        let mut start = None;
+
+        for scheme in &SCHEMES {
+            let needle: Vec<char> = if *scheme == "mailto" || *scheme == "news" {
+                format!("{}:", scheme).chars().collect()
+            } else {
+                format!("{}://", scheme).chars().collect()
+            };
+
+            if needle.len() > end {
+                continue;
+            }
+
+            for needle_start in 0..=(end - needle.len()) {
+                if needle_start > self.origin {
+                    break;
+                }
+
+                if self.buffer[needle_start..needle_start + needle.len()] == needle[..] {
+                    start = Some(start.map_or(needle_start, |start: usize| start.max(needle_start)));
+                }
+            }
+        }
+
+        start.map(|start| (self.origin - start, end - self.origin - 1))
+    }
+}
+
+#[inline]
+fn is_deny_end(c: char) -> bool {
+    matches!(c, '.' | ',' | ';' | ':' | '?' | '!' | '(' | '/')
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 enum SurroundCharacter {
     Bracket(char, char),
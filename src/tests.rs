@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::{Parser, State, SCHEMES};
+use crate::{CursorParser, Parser, ParserState, State, SCHEMES};
 
 #[test]
 fn no_scheme_conflicts() {
@@ -118,6 +118,82 @@ fn multiple_urls() {
     exact_url_match(input, result_map);
 }
 
+#[test]
+fn custom_schemes() {
+    assert_eq!(max_len_with_schemes("magnet://example", &["magnet", "ipfs"]), Some(16));
+    assert_eq!(max_len_with_schemes("ipfs://example", &["magnet", "ipfs"]), Some(14));
+    assert_eq!(max_len_with_schemes("https://example.org", &["magnet", "ipfs"]), None);
+}
+
+#[test]
+fn custom_schemes_with_conflict() {
+    // "ttp" is a suffix of "http", so the parser must keep scanning past "ttp://" instead of
+    // stopping early, in case the full word turns out to be "http" instead.
+    assert_eq!(max_len_with_schemes("test http://example.org", &["ttp", "http"]), Some(18));
+    assert_eq!(max_len_with_schemes("test ttp://example.org", &["ttp", "http"]), Some(17));
+}
+
+#[test]
+fn display_width_counts_columns() {
+    assert_eq!(max_display_width("https://example.org"), Some(19));
+    assert_eq!(max_display_width("https://例.org"), Some(14));
+}
+
+#[test]
+fn display_width_skips_combining_marks() {
+    assert_eq!(max_display_width("https://example.org/test\u{0301}ing"), Some(27));
+}
+
+#[test]
+fn capture_and_display_width_combine() {
+    let mut parser = Parser::new().with_capture().with_display_width();
+    let mut captured = None;
+    let mut reported_len = None;
+
+    for c in "https://例.org".chars().rev() {
+        if let ParserState::Url(len) = parser.advance(c) {
+            captured = parser.captured().map(String::from);
+            reported_len = Some(len);
+        }
+    }
+
+    // "例" is a single character but occupies two display columns.
+    assert_eq!(reported_len, Some(14));
+    assert_eq!(captured.as_deref(), Some("https://例.org"));
+}
+
+#[test]
+fn captures_url_text() {
+    assert_eq!(captured_url("before https://example.org after"), Some("https://example.org".into()));
+}
+
+#[test]
+fn captures_url_text_with_surround() {
+    assert_eq!(captured_url("[https://example.org]"), Some("https://example.org".into()));
+    assert_eq!(captured_url("https://example.org/test'ing'"), Some("https://example.org/test'ing'".into()));
+}
+
+#[test]
+fn captures_nothing_without_match() {
+    assert_eq!(captured_url("no url in here"), None);
+}
+
+#[test]
+fn authority_validation_accepts_valid_hosts() {
+    assert_eq!(max_len_with_authority_validation("https://example.org"), Some(19));
+    assert_eq!(max_len_with_authority_validation("https://sub.example.org:8080"), Some(28));
+    assert_eq!(max_len_with_authority_validation("https://[2001:db8:a0b:12f0::1]:80"), Some(33));
+}
+
+#[test]
+fn authority_validation_rejects_malformed_hosts() {
+    assert_eq!(max_len_with_authority_validation("https://exa,mple.org"), None);
+    assert_eq!(max_len_with_authority_validation("https://:80"), None);
+    assert_eq!(max_len_with_authority_validation("https://.org"), None);
+    assert_eq!(max_len_with_authority_validation("https://[zzzz]:80"), None);
+    assert_eq!(max_len_with_authority_validation("https://[2001:db8::1:80"), None);
+}
+
 #[test]
 fn reset_on_match() {
     let mut parser = Parser::new();
@@ -129,11 +205,86 @@ fn reset_on_match() {
     assert_eq!(parser.state, State::Default);
 }
 
+#[test]
+fn cursor_grows_both_directions() {
+    assert_eq!(cursor_url("before https://example.org after", 14), Some((7, 11)));
+}
+
+#[test]
+fn cursor_stops_at_illegal() {
+    assert_eq!(cursor_url("https://example.org\u{00}evil", 0), Some((0, 18)));
+}
+
+#[test]
+fn cursor_stops_at_unmatched_surround() {
+    assert_eq!(cursor_url("(https://example.org).", 10), Some((9, 9)));
+}
+
+#[test]
+fn cursor_trims_deny_end() {
+    assert_eq!(cursor_url("https://example.org.", 0), Some((0, 18)));
+}
+
+#[test]
+fn cursor_origin_not_in_url() {
+    assert_eq!(cursor_url("before https://example.org after", 2), None);
+}
+
+#[test]
+fn cursor_prefers_occurrence_containing_origin() {
+    let input = "http://example.com/redirect?next=http://evil.com";
+    assert_eq!(cursor_url(input, 10), Some((10, 37)));
+}
+
+#[test]
+fn cursor_multibyte_prefix_uses_char_offsets() {
+    assert_eq!(cursor_url("中https://example.org", 9), Some((8, 10)));
+}
+
+/// Feed `input` into a [`CursorParser`] with the origin at `origin_index`, growing outward until
+/// both sides stop, then return the resulting URL span.
+fn cursor_url(input: &str, origin_index: usize) -> Option<(usize, usize)> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut parser = CursorParser::new();
+
+    parser.advance_origin(chars[origin_index]);
+
+    for &c in chars[origin_index + 1..].iter() {
+        if !parser.advance_right(c) {
+            break;
+        }
+    }
+
+    for &c in chars[..origin_index].iter().rev() {
+        if !parser.advance_left(c) {
+            break;
+        }
+    }
+
+    parser.url()
+}
+
+fn captured_url(input: &str) -> Option<String> {
+    let mut parser = Parser::new().with_capture();
+    let mut result = None;
+
+    for c in input.chars().rev() {
+        if let ParserState::Url(_) = parser.advance(c) {
+            result = parser.captured().map(String::from);
+        }
+    }
+
+    result
+}
+
 fn exact_url_match(input: &str, result_map: HashMap<usize, Option<u16>>) {
     let mut parser = Parser::new();
 
     for (i, c) in input.chars().rev().enumerate() {
-        let result = parser.advance(c);
+        let result = match parser.advance(c) {
+            ParserState::Url(len) => Some(len as u16),
+            _ => None,
+        };
 
         if let Some(expected) = result_map.get(&i) {
             assert_eq!(&result, expected);
@@ -148,8 +299,47 @@ fn max_len(input: &str) -> Option<u16> {
     let mut url_len = None;
 
     for c in input.chars().rev() {
-        if let Some(len) = parser.advance(c) {
-            url_len = Some(len);
+        if let ParserState::Url(len) = parser.advance(c) {
+            url_len = Some(len as u16);
+        }
+    }
+
+    url_len
+}
+
+fn max_len_with_schemes(input: &str, schemes: &[&str]) -> Option<u16> {
+    let mut parser = Parser::new().with_schemes(schemes);
+    let mut url_len = None;
+
+    for c in input.chars().rev() {
+        if let ParserState::Url(len) = parser.advance(c) {
+            url_len = Some(len as u16);
+        }
+    }
+
+    url_len
+}
+
+fn max_len_with_authority_validation(input: &str) -> Option<u16> {
+    let mut parser = Parser::new().with_authority_validation();
+    let mut url_len = None;
+
+    for c in input.chars().rev() {
+        if let ParserState::Url(len) = parser.advance(c) {
+            url_len = Some(len as u16);
+        }
+    }
+
+    url_len
+}
+
+fn max_display_width(input: &str) -> Option<u16> {
+    let mut parser = Parser::new().with_display_width();
+    let mut url_len = None;
+
+    for c in input.chars().rev() {
+        if let ParserState::Url(len) = parser.advance(c) {
+            url_len = Some(len as u16);
         }
     }
 
@@ -160,7 +350,7 @@ fn position(input: &str) -> (usize, usize) {
     let mut parser = Parser::new();
     let mut position_right = 0usize;
     let mut position_left = 0usize;
-    let mut url_len = None;
+    let mut url_len: Option<usize> = None;
 
     for c in input.chars().rev() {
         if url_len.is_some() {
@@ -169,13 +359,13 @@ fn position(input: &str) -> (usize, usize) {
             position_right += 1;
         }
 
-        if let Some(len) = parser.advance(c) {
+        if let ParserState::Url(len) = parser.advance(c) {
             url_len = Some(len);
         }
     }
 
     if let Some(url_len) = url_len {
-        position_right = position_right.saturating_sub(url_len as usize);
+        position_right = position_right.saturating_sub(url_len);
     }
 
     (position_left, position_right)